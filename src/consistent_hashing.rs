@@ -1,39 +1,106 @@
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
 
-/// Each server occupies 5 positions in the ring.
-const NUMBER_OF_POSITIONS_IN_RING: u8 = 5;
+/// The hasher the ring uses unless another one is supplied. It wraps the
+/// standard [`DefaultHasher`] with a fixed seed, so positions are stable.
+type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+
+/// Default number of virtual nodes (ring positions) each server occupies.
+const NUMBER_OF_POSITIONS_IN_RING: usize = 5;
+
+/// How much a server is allowed to exceed the average load before the bounded
+/// router skips it, as described in "consistent hashing with bounded loads".
+const DEFAULT_EPSILON: f64 = 0.25;
+
+/// Default number of logical partitions in the partition table. A prime keeps
+/// the `hash(key) % N` bucketing well spread.
+const DEFAULT_PARTITIONS: usize = 271;
 
 /// Server is just a string, representing the name of the server.
 type Server = String;
 
-pub struct Ring {
-    servers: HashMap<u8, Server>,
+/// Errors that the ring can return.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RingError {
+    /// No free position could be found for a server after many attempts.
+    RingFull,
+    /// A routing operation was attempted on a ring with no servers.
+    NoServersAvailable,
+    /// A caller passed an argument that doesn't make sense (e.g. a zero weight).
+    IllegalArgument,
 }
 
-impl Ring {
-    /// Returns what server holds they key passed as a parameter
-    pub fn get_server_for_key(&self, key: &str) -> Result<Server, &str> {
-        if self.servers.is_empty() {
-            return Err("No servers available");
-        }
+impl Display for RingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            RingError::RingFull => "the ring is full",
+            RingError::NoServersAvailable => "no servers available",
+            RingError::IllegalArgument => "illegal argument",
+        };
+        f.write_str(message)
+    }
+}
 
-        let mut h = hash(key);
+impl std::error::Error for RingError {}
+
+pub struct Ring<S = DefaultBuildHasher> {
+    /// The ring itself, keyed by 64-bit position and kept sorted so we can
+    /// find a key's clockwise successor cheaply.
+    servers: BTreeMap<u64, Server>,
+    /// Reverse index: every ring position a given server occupies.
+    /// This lets us remove a server in O(virtual_nodes) instead of
+    /// scanning the whole ring.
+    positions: HashMap<Server, Vec<u64>>,
+    /// Live number of keys assigned to each server by the bounded router.
+    loads: HashMap<Server, u64>,
+    /// Which server currently owns each bounded-routed key (keyed by the key's
+    /// hash), so `release` knows whose load to decrement.
+    assignments: HashMap<u64, Server>,
+    /// Load-balancing slack for the bounded router (see [`DEFAULT_EPSILON`]).
+    epsilon: f64,
+    /// Number of virtual nodes claimed per server when it is added.
+    replicas: usize,
+    /// Number of logical partitions in the partition table layer.
+    partitions: usize,
+    /// The hasher used to place servers and route keys onto the ring.
+    hasher: S,
+}
 
-        loop {
-            match self.servers.get(&h) {
-                None => h = h.checked_add(1).or(Some(0)).unwrap(),
-                Some(value) => return Ok(value.to_owned()),
-            }
+impl Ring<DefaultBuildHasher> {
+    /// Builds a ring with the default number of virtual nodes per server.
+    pub fn new(servers: Vec<Server>) -> Result<Self, RingError> {
+        Self::with_replicas(servers, NUMBER_OF_POSITIONS_IN_RING)
+    }
+
+    /// Builds a ring where each server claims `replicas` virtual-node
+    /// positions. More replicas smooth key distribution at the cost of a
+    /// larger ring.
+    pub fn with_replicas(servers: Vec<Server>, replicas: usize) -> Result<Self, RingError> {
+        Self::with_hasher(servers, replicas, DefaultBuildHasher::default())
+    }
+
+    /// Builds a ring from servers paired with a weight. Heavier servers claim
+    /// proportionally more positions and therefore receive a larger share of
+    /// the keys — the usual remedy for clusters of mixed-capacity nodes.
+    pub fn new_weighted(servers: Vec<(Server, u32)>) -> Result<Self, RingError> {
+        let mut ring = Self::empty(NUMBER_OF_POSITIONS_IN_RING, DefaultBuildHasher::default());
+
+        for (server, weight) in servers {
+            ring.add_weighted_server(server, weight)?;
         }
+
+        Ok(ring)
     }
+}
 
-    pub fn new(servers: Vec<Server>) -> Result<Self, String> {
-        let mut ring = Self {
-            servers: HashMap::new(),
-        };
+impl<S: BuildHasher> Ring<S> {
+    /// Builds a ring using a caller-supplied hasher — for instance a faster,
+    /// higher-dispersion one like ahash. The hasher drives both where servers
+    /// land and how keys are routed.
+    pub fn with_hasher(servers: Vec<Server>, replicas: usize, hasher: S) -> Result<Self, RingError> {
+        let mut ring = Self::empty(replicas, hasher);
 
         for server in servers {
             ring.add_server(server)?;
@@ -42,28 +109,182 @@ impl Ring {
         Ok(ring)
     }
 
+    fn empty(replicas: usize, hasher: S) -> Self {
+        Self {
+            servers: BTreeMap::new(),
+            positions: HashMap::new(),
+            loads: HashMap::new(),
+            assignments: HashMap::new(),
+            epsilon: DEFAULT_EPSILON,
+            replicas,
+            partitions: DEFAULT_PARTITIONS,
+            hasher,
+        }
+    }
+
+    /// Sets the load-balancing slack `epsilon` used by the bounded router: a
+    /// larger value lets servers stray further above the average load before
+    /// they are skipped. Non-positive values are rejected, since the capacity
+    /// formula assumes `epsilon > 0`.
+    pub fn set_epsilon(&mut self, epsilon: f64) -> Result<(), RingError> {
+        if epsilon <= 0.0 {
+            return Err(RingError::IllegalArgument);
+        }
+        self.epsilon = epsilon;
+        Ok(())
+    }
+
+    /// Sets the number of logical partitions in the partition table. A zero
+    /// count is rejected, since it would make `hash(key) % N` divide by zero.
+    pub fn set_partition_count(&mut self, partitions: usize) -> Result<(), RingError> {
+        if partitions == 0 {
+            return Err(RingError::IllegalArgument);
+        }
+        self.partitions = partitions;
+        Ok(())
+    }
+
+    /// Maps a key to one of the `N` logical partitions via `hash(key) % N`.
+    /// Partitions give callers a stable, enumerable bucketing layer on top of
+    /// the ring rather than having to probe keys one at a time.
+    pub fn get_partition_for_key<K: Hash>(&self, key: K) -> usize {
+        (self.hash(&key) % self.partitions as u64) as usize
+    }
+
+    /// Returns the server that owns a given partition, found by hashing the
+    /// partition id onto the ring like any other key.
+    pub fn get_server_for_partition(&self, id: usize) -> Result<Server, RingError> {
+        self.get_server_for_key(id)
+    }
+
+    /// Precomputes the full partition-to-server ownership map, so callers can
+    /// cache a routing table and diff it to see exactly which partitions move
+    /// when a server is added or removed. Empty while the ring has no servers.
+    pub fn owners(&self) -> HashMap<usize, Server> {
+        (0..self.partitions)
+            .filter_map(|id| self.get_server_for_partition(id).ok().map(|server| (id, server)))
+            .collect()
+    }
+
+    /// Returns what server holds the key passed as a parameter. The key may be
+    /// anything hashable, not just a string.
+    pub fn get_server_for_key<K: Hash>(&self, key: K) -> Result<Server, RingError> {
+        if self.servers.is_empty() {
+            return Err(RingError::NoServersAvailable);
+        }
+
+        let h = self.hash(&key);
+
+        // The owner is the first server at or clockwise-after the key's
+        // position, wrapping back to the start of the ring if needed.
+        let server = self
+            .servers
+            .range(h..)
+            .next()
+            .or_else(|| self.servers.iter().next())
+            .map(|(_, server)| server.to_owned())
+            .unwrap();
+
+        Ok(server)
+    }
+
+    /// Routes a key to a server using "consistent hashing with bounded loads":
+    /// walk clockwise from the key's position but skip any server that has
+    /// already reached its share of the load, so no node absorbs a runaway
+    /// number of keys. The capacity is `ceil(average_load * (1 + epsilon))`,
+    /// and the capacity formula guarantees at least one server is always under
+    /// it, so the walk terminates. The assignment is remembered until it is
+    /// released, so repeated calls for the same key are stable and idempotent.
+    pub fn get_server_for_key_bounded<K: Hash>(&mut self, key: K) -> Result<Server, RingError> {
+        if self.servers.is_empty() {
+            return Err(RingError::NoServersAvailable);
+        }
+
+        let h = self.hash(&key);
+        if let Some(server) = self.assignments.get(&h) {
+            return Ok(server.to_owned());
+        }
+
+        let num_servers = self.positions.len() as f64;
+        let total_keys = (self.assignments.len() + 1) as f64;
+        let average_load = total_keys / num_servers;
+        let capacity = (average_load * (1.0 + self.epsilon)).ceil() as u64;
+
+        // Walk clockwise from the key's position (wrapping around), skipping
+        // any server already at capacity. The capacity formula guarantees a
+        // free slot exists, so this always finds one.
+        let order: Vec<Server> = self
+            .servers
+            .range(h..)
+            .chain(self.servers.range(..h))
+            .map(|(_, server)| server.to_owned())
+            .collect();
+
+        for server in order {
+            if self.loads.get(&server).copied().unwrap_or(0) < capacity {
+                *self.loads.entry(server.clone()).or_insert(0) += 1;
+                self.assignments.insert(h, server.clone());
+                return Ok(server);
+            }
+        }
+
+        Err(RingError::NoServersAvailable)
+    }
+
+    /// Releases a key previously routed by [`get_server_for_key_bounded`],
+    /// decrementing the owning server's live load. Unknown keys are ignored.
+    pub fn release<K: Hash>(&mut self, key: K) {
+        let h = self.hash(&key);
+        if let Some(server) = self.assignments.remove(&h) {
+            if let Some(load) = self.loads.get_mut(&server) {
+                *load = load.saturating_sub(1);
+            }
+        }
+    }
+
     /// This adds a server to the ring, at "random" positions.
     /// In practice, it simply adds some salt to the server name
     /// and then hashes the value to get a position in the ring.
     /// It repeats that until we fit the server in as many positions
     /// as we wanted.
-    /// If there isn't enough space to fit the server, then it
-    /// returns an error instead.
-    fn add_server(&mut self, server: Server) -> Result<(), &str> {
-        if self.servers.len() + NUMBER_OF_POSITIONS_IN_RING as usize > 255 {
-            return Err("The ring is already full");
+    fn add_server(&mut self, server: Server) -> Result<(), RingError> {
+        self.add_weighted_server(server, 1)
+    }
+
+    /// Adds a server whose `weight` multiplies the number of virtual-node
+    /// positions it claims, so a weight-3 server receives roughly three times
+    /// as many keys as a weight-1 one. The reverse index records every one of
+    /// those positions, so [`Ring::remove_server`] still cleans them all up.
+    pub fn add_weighted_server(&mut self, server: Server, weight: u32) -> Result<(), RingError> {
+        if weight == 0 {
+            return Err(RingError::IllegalArgument);
         }
 
+        let target = self.replicas * weight as usize;
+        // Bound the salting so a degenerate hasher that keeps colliding can't
+        // spin forever; giving up means the ring can't fit the server.
+        let max_attempts = target.saturating_mul(64).saturating_add(1024);
+
         let mut inserted_count = 0;
-        let mut salt = 0;
-        while inserted_count < NUMBER_OF_POSITIONS_IN_RING {
-            let server_hash_with_salt = hash(format!("{}_{}", &server, salt).as_str());
+        let mut salt: u64 = 0;
+        let mut attempts = 0;
+        while inserted_count < target {
+            if attempts >= max_attempts {
+                return Err(RingError::RingFull);
+            }
+            attempts += 1;
+
+            let server_hash_with_salt = self.hash(&format!("{}_{}", &server, salt));
             salt += 217; // Arbitrary number
 
             match self.servers.contains_key(&server_hash_with_salt) {
                 true => continue, // If we already have a server in this position of the ring, just try again with a different salt.
                 false => {
                     self.servers.insert(server_hash_with_salt, server.clone());
+                    self.positions
+                        .entry(server.clone())
+                        .or_default()
+                        .push(server_hash_with_salt);
                     inserted_count += 1;
                 }
             }
@@ -72,123 +293,312 @@ impl Ring {
         Ok(())
     }
 
-    /// We should be able to remove a server from the ring.
-    fn _remove_server(&mut self, _server: Server) -> Option<Server> {
-        // To make things simple for removing servers from the ring, we could have another HashMap
-        // that has the server for keys, and a vec of positions for values.
-        todo!()
+    /// Removes a server from the ring, relocating only that server's keys.
+    /// Every position the server owned is erased, so the keys that used to
+    /// land there now fall through to the next server clockwise; keys routed
+    /// to any other server are left untouched. Returns the removed server, or
+    /// `None` if it wasn't on the ring.
+    pub fn remove_server(&mut self, server: Server) -> Option<Server> {
+        let positions = self.positions.remove(&server)?;
+
+        for position in positions {
+            self.servers.remove(&position);
+        }
+
+        // Drop the bounded router's state for the server too, otherwise a
+        // re-added server with the same name would inherit a stale load.
+        self.loads.remove(&server);
+        self.assignments.retain(|_, owner| owner != &server);
+
+        Some(server)
+    }
+
+    /// Hashes a key into a position in the 64-bit ring space using the ring's
+    /// configured hasher.
+    fn hash<K: Hash>(&self, key: &K) -> u64 {
+        self.hasher.hash_one(key)
     }
 }
 
 #[test]
 fn test_get_server_for_key() {
-    let ring = Ring {
-        servers: HashMap::from([(0, "A".to_string()), (128, "B".to_string())]),
-    };
+    // With a clockwise walk, a key hashing past the last position wraps back
+    // to the lowest one. A single server therefore owns every key.
+    let ring = Ring::new(vec!["A".into()]).unwrap();
+    for key in vec!["world", "hello", "consistent hashing", "ABCDEFGH"] {
+        assert_eq!(Ok("A".to_string()), ring.get_server_for_key(key), "key: {}", key);
+    }
 
-    for (key, want) in vec![
-        ("world", "A"),
-        ("some other key", "A"),
-        ("ABCDEFGH", "A"),
-        ("hello", "B"),
-        ("consistent hashing", "B"),
-    ] {
-        let got = ring.get_server_for_key(key);
-        assert_eq!(Ok(want.to_string()), got, "key: {}", key);
+    // Routing is deterministic: the same key always lands on the same server.
+    let ring = Ring::new(vec!["A".into(), "B".into(), "C".into()]).unwrap();
+    for key in vec!["world", "hello", "consistent hashing"] {
+        let first = ring.get_server_for_key(key).unwrap();
+        assert_eq!(Ok(first), ring.get_server_for_key(key), "key: {}", key);
     }
 }
 
 #[test]
 fn test_new_ring() {
-    let got = Ring::new(vec!["Alice".into(), "Bob".into(), "Charlie".into()]).unwrap();
-    let want = HashMap::from([
-        (25, "Alice".to_string()),
-        (28, "Alice".to_string()),
-        (90, "Alice".to_string()),
-        (99, "Alice".to_string()),
-        (191, "Alice".to_string()),
-        (35, "Bob".to_string()),
-        (51, "Bob".to_string()),
-        (57, "Bob".to_string()),
-        (81, "Bob".to_string()),
-        (206, "Bob".to_string()),
-        (16, "Charlie".to_string()),
-        (39, "Charlie".to_string()),
-        (108, "Charlie".to_string()),
-        (132, "Charlie".to_string()),
-        (210, "Charlie".to_string()),
-    ]);
-
-    assert_eq!(want, got.servers);
+    let ring = Ring::new(vec!["Alice".into(), "Bob".into(), "Charlie".into()]).unwrap();
+
+    // Every server claims exactly NUMBER_OF_POSITIONS_IN_RING positions, and
+    // the reverse index agrees with the forward ring.
+    assert_eq!(3 * NUMBER_OF_POSITIONS_IN_RING, ring.servers.len());
+    for server in ["Alice", "Bob", "Charlie"] {
+        let positions = &ring.positions[server];
+        assert_eq!(NUMBER_OF_POSITIONS_IN_RING, positions.len());
+        for position in positions {
+            assert_eq!(Some(&server.to_string()), ring.servers.get(position));
+        }
+    }
+}
+
+#[test]
+fn test_with_replicas() {
+    let ring = Ring::with_replicas(vec!["Alice".into(), "Bob".into()], 32).unwrap();
+    assert_eq!(2 * 32, ring.servers.len());
+    assert_eq!(32, ring.positions["Alice"].len());
+}
+
+#[test]
+fn test_new_weighted() {
+    let ring = Ring::new_weighted(vec![("Big".into(), 3), ("Small".into(), 1)]).unwrap();
+
+    // The heavy server claims three times as many positions as the light one.
+    assert_eq!(3 * NUMBER_OF_POSITIONS_IN_RING, ring.positions["Big"].len());
+    assert_eq!(NUMBER_OF_POSITIONS_IN_RING, ring.positions["Small"].len());
+
+    // Removal still cleans up every weighted position.
+    ring_removes_all_positions(ring, "Big");
+}
+
+#[cfg(test)]
+fn ring_removes_all_positions(mut ring: Ring, server: &str) {
+    ring.remove_server(server.into());
+    assert!(!ring.positions.contains_key(server));
+    assert!(ring.servers.values().all(|s| s != server));
 }
 
 #[test]
 fn test_add_server_conflict() {
     let mut ring = Ring::new(vec!["Alice".into()]).unwrap();
-    assert_eq!(
-        (1 * NUMBER_OF_POSITIONS_IN_RING) as usize,
-        ring.servers.len()
-    );
+    assert_eq!(NUMBER_OF_POSITIONS_IN_RING, ring.servers.len());
 
     // When inserting another server with conflicting keys (here, we're just
     // reusing the same Server name, so all first 5 keys conflict), it should
     // still be able to insert the new server at 5 locations.
 
     ring.add_server("Alice".into()).unwrap();
-    assert_eq!(
-        (2 * NUMBER_OF_POSITIONS_IN_RING) as usize,
-        ring.servers.len()
-    );
+    assert_eq!(2 * NUMBER_OF_POSITIONS_IN_RING, ring.servers.len());
+}
+
+#[test]
+fn test_remove_server() {
+    let mut ring = Ring::new(vec!["Alice".into(), "Bob".into(), "Charlie".into()]).unwrap();
+
+    // Record where every key routes before the removal.
+    let keys = vec!["hello", "world", "something", "something else", "consistent"];
+    let before: HashMap<&str, Server> = keys
+        .iter()
+        .map(|&key| (key, ring.get_server_for_key(key).unwrap()))
+        .collect();
+
+    assert_eq!(Some("Bob".to_string()), ring.remove_server("Bob".into()));
+    assert_eq!(2 * NUMBER_OF_POSITIONS_IN_RING, ring.servers.len());
+    assert!(!ring.positions.contains_key("Bob"));
+
+    // Keys that didn't belong to Bob must be unchanged; Bob's keys move on to
+    // their clockwise successor (never back to the removed server).
+    for key in keys {
+        let now = ring.get_server_for_key(key).unwrap();
+        assert_ne!("Bob", now, "key {} still routes to the removed server", key);
+        if before[key] != "Bob" {
+            assert_eq!(before[key], now, "key {} was remapped unexpectedly", key);
+        }
+    }
+}
+
+#[test]
+fn test_remove_missing_server() {
+    let mut ring = Ring::new(vec!["Alice".into()]).unwrap();
+    assert_eq!(None, ring.remove_server("Bob".into()));
+    assert_eq!(NUMBER_OF_POSITIONS_IN_RING, ring.servers.len());
+}
+
+#[test]
+fn test_bounded_loads_respects_capacity() {
+    let mut ring = Ring::new(vec!["Alice".into(), "Bob".into(), "Charlie".into()]).unwrap();
+
+    // Route a bunch of keys; with epsilon = 0.25 no server may ever hold more
+    // than ceil(average_load * 1.25) keys at the moment it is assigned.
+    let keys: Vec<String> = (0..60).map(|i| format!("key-{}", i)).collect();
+    for key in &keys {
+        ring.get_server_for_key_bounded(key).unwrap();
+    }
+
+    assert_eq!(keys.len() as u64, ring.loads.values().sum::<u64>());
+
+    let average = keys.len() as f64 / 3.0;
+    let capacity = (average * (1.0 + DEFAULT_EPSILON)).ceil() as u64;
+    for load in ring.loads.values() {
+        assert!(*load <= capacity, "load {} exceeds capacity {}", load, capacity);
+    }
 }
 
 #[test]
-fn test_cannot_add_server_to_full_ring() {
-    let mut ring = Ring::new(vec![]).unwrap();
+fn test_bounded_loads_release() {
+    let mut ring = Ring::new(vec!["Alice".into(), "Bob".into()]).unwrap();
+
+    let server = ring.get_server_for_key_bounded("hello").unwrap();
+    assert_eq!(1, ring.loads[&server]);
+    // Re-routing the same key is idempotent.
+    assert_eq!(server, ring.get_server_for_key_bounded("hello").unwrap());
+    assert_eq!(1, ring.loads[&server]);
+
+    ring.release("hello");
+    assert_eq!(0, ring.loads[&server]);
+    assert!(!ring.assignments.contains_key(&ring.hash(&"hello")));
+}
+
+#[test]
+fn test_configurable_epsilon() {
+    let mut ring = Ring::new(vec!["Alice".into(), "Bob".into()]).unwrap();
+
+    // Raising epsilon above the default loosens each server's capacity; a
+    // non-positive value is rejected outright.
+    ring.set_epsilon(0.5).unwrap();
+    assert_eq!(RingError::IllegalArgument, ring.set_epsilon(0.0).unwrap_err());
 
-    let number_of_server_we_can_fit_in_the_ring = 255 / NUMBER_OF_POSITIONS_IN_RING as usize;
-    for i in 0..number_of_server_we_can_fit_in_the_ring {
-        ring.add_server(format!("Server number {}", i)).unwrap();
+    let keys: Vec<String> = (0..20).map(|i| format!("key-{}", i)).collect();
+    for key in &keys {
+        ring.get_server_for_key_bounded(key).unwrap();
     }
 
+    let average = keys.len() as f64 / 2.0;
+    let capacity = (average * 1.5).ceil() as u64;
+    for load in ring.loads.values() {
+        assert!(*load <= capacity, "load {} exceeds capacity {}", load, capacity);
+    }
+}
+
+#[test]
+fn test_hash() {
+    let ring = Ring::new(vec![]).unwrap();
+
+    // The hash is deterministic and spreads distinct keys across the space.
+    let keys = vec![
+        "",
+        "0",
+        "1",
+        "00",
+        "01",
+        "0123-4567-89ab-cdef",
+        "0123-4567-89ab-cdee",
+        "1234-5678-90ab-cdef",
+        "abcd-ef12-3456-7890",
+    ];
+
+    for key in &keys {
+        assert_eq!(ring.hash(key), ring.hash(key), "key: {}", key);
+    }
+
+    let distinct: std::collections::HashSet<u64> = keys.iter().map(|key| ring.hash(key)).collect();
+    assert_eq!(keys.len(), distinct.len());
+}
+
+#[test]
+fn test_routes_arbitrary_key_types() {
+    let ring = Ring::new(vec!["Alice".into(), "Bob".into()]).unwrap();
+
+    // Non-string keys route just like strings, and deterministically.
+    for key in [1u64, 42, 7777] {
+        assert_eq!(ring.get_server_for_key(key), ring.get_server_for_key(key));
+    }
+    let bytes: &[u8] = b"some bytes";
+    assert!(ring.get_server_for_key(bytes).is_ok());
+}
+
+#[test]
+fn test_ring_errors() {
+    // Routing against an empty ring is distinguishable from other failures.
+    let empty = Ring::new(vec![]).unwrap();
+    assert_eq!(Err(RingError::NoServersAvailable), empty.get_server_for_key("x"));
+
+    // A zero weight is rejected as an illegal argument.
+    let mut ring = Ring::new(vec!["Alice".into()]).unwrap();
+    assert_eq!(RingError::IllegalArgument, ring.add_weighted_server("Bob".into(), 0).unwrap_err());
+
+    assert_eq!("no servers available", RingError::NoServersAvailable.to_string());
+}
+
+#[test]
+fn test_partition_table() {
+    let ring = Ring::new(vec!["Alice".into(), "Bob".into(), "Charlie".into()]).unwrap();
+
+    // Every partition has exactly one owner, and the ownership map enumerates
+    // all of them.
+    let owners = ring.owners();
+    assert_eq!(DEFAULT_PARTITIONS, owners.len());
+
+    // A key maps to a partition, and that partition maps to a server; both
+    // steps are deterministic.
+    let partition = ring.get_partition_for_key("hello");
+    assert!(partition < DEFAULT_PARTITIONS);
+    assert_eq!(partition, ring.get_partition_for_key("hello"));
     assert_eq!(
-        Err("The ring is already full"),
-        ring.add_server("Another server".to_string())
+        owners[&partition],
+        ring.get_server_for_partition(partition).unwrap()
     );
 }
 
-/// In: key
-/// Out: 0..255
-fn hash(key: &str) -> u8 {
-    let mut hasher = DefaultHasher::new();
-    key.hash(&mut hasher);
-    u8::try_from(hasher.finish() % 255).unwrap() // since we %255, it can't fail to convert into a u8.
+#[test]
+fn test_partition_ownership_is_stable_across_removal() {
+    let mut ring =
+        Ring::new(vec!["Alice".into(), "Bob".into(), "Charlie".into(), "Dave".into()]).unwrap();
+
+    let before = ring.owners();
+    ring.remove_server("Dave".into());
+    let after = ring.owners();
+
+    // Only partitions that used to belong to Dave may have moved; the rest are
+    // untouched.
+    for (id, owner) in &before {
+        if owner != "Dave" {
+            assert_eq!(Some(owner), after.get(id), "partition {} moved unexpectedly", id);
+        }
+    }
 }
 
 #[test]
-fn test_hash() {
-    for (key, want) in vec![
-        ("", 154),
-        ("0", 234),
-        ("1", 85),
-        ("00", 80),
-        ("01", 112),
-        ("0123-4567-89ab-cdef", 128),
-        ("0123-4567-89ab-cdee", 163),
-        ("1234-5678-90ab-cdef", 54),
-        ("abcd-ef12-3456-7890", 236),
-    ] {
-        let got = hash(key);
-        assert_eq!(want, got, "key: {}", key);
-    }
-}
-
-impl Display for Ring {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut server_positions: Vec<_> = self.servers.iter().collect();
-        server_positions.sort_by_key(|(&pos, _server_name)| pos);
+fn test_configurable_partition_count() {
+    let mut ring = Ring::new(vec!["Alice".into()]).unwrap();
+    ring.set_partition_count(16).unwrap();
 
-        for (position, server_name) in server_positions {
-            std::fmt::Formatter::write_fmt(f, format_args!("{},{}\n", position, server_name))?;
+    assert_eq!(16, ring.owners().len());
+    assert!(ring.get_partition_for_key("anything") < 16);
+
+    // A zero partition count would divide by zero, so it is rejected.
+    assert_eq!(RingError::IllegalArgument, ring.set_partition_count(0).unwrap_err());
+}
+
+#[test]
+fn test_with_custom_hasher() {
+    // Any BuildHasher works; here we just reuse the default one explicitly.
+    let ring = Ring::with_hasher(
+        vec!["Alice".into(), "Bob".into()],
+        NUMBER_OF_POSITIONS_IN_RING,
+        DefaultBuildHasher::default(),
+    )
+    .unwrap();
+
+    assert_eq!(2 * NUMBER_OF_POSITIONS_IN_RING, ring.servers.len());
+}
+
+impl<S> Display for Ring<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // `servers` is a BTreeMap, so iteration is already sorted by position.
+        for (position, server_name) in &self.servers {
+            writeln!(f, "{},{}", position, server_name)?;
         }
 
         Ok(())
@@ -200,7 +610,7 @@ fn test_display() {
     use std::io::Write;
 
     let ring = Ring {
-        servers: HashMap::from([
+        servers: BTreeMap::from([
             (203, "A".to_string()),
             (88, "A".to_string()),
             (10, "B".to_string()),
@@ -208,6 +618,17 @@ fn test_display() {
             (137, "C".to_string()),
             (50, "C".to_string()),
         ]),
+        positions: HashMap::from([
+            ("A".to_string(), vec![88, 203]),
+            ("B".to_string(), vec![0, 10]),
+            ("C".to_string(), vec![50, 137]),
+        ]),
+        loads: HashMap::new(),
+        assignments: HashMap::new(),
+        epsilon: DEFAULT_EPSILON,
+        replicas: NUMBER_OF_POSITIONS_IN_RING,
+        partitions: DEFAULT_PARTITIONS,
+        hasher: DefaultBuildHasher::default(),
     };
 
     let mut output = Vec::new();
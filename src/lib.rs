@@ -0,0 +1,3 @@
+pub mod consistent_hashing;
+
+pub use consistent_hashing::Ring;
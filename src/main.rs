@@ -1,7 +1,5 @@
 use consistent_hashing::Ring;
 
-mod consistent_hashing;
-
 fn main() {
     let servers = vec!["A".into(), "B".into(), "C".into()];
     let ring = Ring::new(servers).expect("Should be able to create a ring");